@@ -1,6 +1,6 @@
 use jni::JNIEnv;
-use jni::objects::{JClass, JString, JIntArray};
-use jni::sys::{jlong, jintArray, jstring};
+use jni::objects::{JClass, JString, JIntArray, JObjectArray, JObject};
+use jni::sys::{jlong, jintArray, jstring, jboolean, jobjectArray, jint};
 use std::ffi::{CString, CStr};
 use std::ptr;
 
@@ -23,6 +23,13 @@ extern "C" {
         tokens_out: *mut *mut u32,
         tokens_len: *mut usize,
     ) -> HarmonyResult;
+    fn harmony_encoding_render_conversation(
+        wrapper: *const std::ffi::c_void,
+        messages: *const HarmonyMessage,
+        messages_len: usize,
+        tokens_out: *mut *mut u32,
+        tokens_len: *mut usize,
+    ) -> HarmonyResult;
     fn harmony_encoding_decode(
         wrapper: *const std::ffi::c_void,
         tokens: *const u32,
@@ -35,6 +42,67 @@ extern "C" {
     ) -> HarmonyResult;
     fn harmony_free_string(s: *mut std::os::raw::c_char);
     fn harmony_free_tokens(tokens: *mut u32, len: usize);
+
+    // Incremental (streaming) parser: decodes one generated token at a time,
+    // tracking role/channel state across the call boundary so the caller can
+    // split hidden `analysis` chain-of-thought from the user-facing `final`
+    // channel as tokens arrive.
+    fn harmony_stream_parser_new(wrapper: *const std::ffi::c_void) -> *mut std::ffi::c_void;
+    fn harmony_stream_parser_free(parser: *mut std::ffi::c_void);
+    fn harmony_stream_parser_push(
+        parser: *mut std::ffi::c_void,
+        token: u32,
+        text_out: *mut *mut std::os::raw::c_char,
+    ) -> HarmonyResult;
+    fn harmony_stream_parser_channel_boundary(parser: *const std::ffi::c_void) -> bool;
+    fn harmony_stream_parser_message_boundary(parser: *const std::ffi::c_void) -> bool;
+    fn harmony_stream_parser_current_channel(
+        parser: *const std::ffi::c_void,
+    ) -> *mut std::os::raw::c_char;
+    fn harmony_stream_parser_current_role(
+        parser: *const std::ffi::c_void,
+    ) -> *mut std::os::raw::c_char;
+
+    // Tool/function calling: bake developer-declared tools into the rendered
+    // prompt, and scan decoded `commentary to={tool}<|call|>` segments back out
+    // of generated output.
+    fn harmony_encoding_render_with_tools(
+        wrapper: *const std::ffi::c_void,
+        developer_instruction: *const std::os::raw::c_char,
+        tools: *const HarmonyToolDef,
+        tools_len: usize,
+        tokens_out: *mut *mut u32,
+        tokens_len: *mut usize,
+    ) -> HarmonyResult;
+    fn harmony_decode_tool_calls(
+        wrapper: *const std::ffi::c_void,
+        tokens: *const u32,
+        tokens_len: usize,
+        names_out: *mut *mut *mut std::os::raw::c_char,
+        args_out: *mut *mut *mut std::os::raw::c_char,
+        count_out: *mut usize,
+    ) -> HarmonyResult;
+    fn harmony_free_tool_calls(
+        names: *mut *mut std::os::raw::c_char,
+        args: *mut *mut std::os::raw::c_char,
+        count: usize,
+    );
+
+    // Parameterized render: like harmony_encoding_render_prompt, but also lets the
+    // caller set the system-message metadata (model identity, knowledge cutoff,
+    // current date, reasoning effort) that Harmony injects into the system header.
+    fn harmony_encoding_render_prompt_ex(
+        wrapper: *const std::ffi::c_void,
+        system_msg: *const std::os::raw::c_char,
+        user_msg: *const std::os::raw::c_char,
+        assistant_prefix: *const std::os::raw::c_char,
+        model_identity: *const std::os::raw::c_char,
+        knowledge_cutoff: *const std::os::raw::c_char,
+        current_date: *const std::os::raw::c_char,
+        reasoning_effort: i32,
+        tokens_out: *mut *mut u32,
+        tokens_len: *mut usize,
+    ) -> HarmonyResult;
 }
 
 #[repr(C)]
@@ -43,30 +111,94 @@ struct HarmonyResult {
     error_message: *mut std::os::raw::c_char,
 }
 
+/// A single turn of a Harmony conversation, mirrored into the C layout
+/// expected by `harmony_encoding_render_conversation`. `channel` is null
+/// when the message doesn't target a specific channel.
+#[repr(C)]
+struct HarmonyMessage {
+    role: *const std::os::raw::c_char,
+    channel: *const std::os::raw::c_char,
+    content: *const std::os::raw::c_char,
+}
+
+/// A callable tool declaration, mirrored into the C layout expected by
+/// `harmony_encoding_render_with_tools`. `schema` is the tool's JSON Schema
+/// for its arguments, serialized as a string.
+#[repr(C)]
+struct HarmonyToolDef {
+    name: *const std::os::raw::c_char,
+    description: *const std::os::raw::c_char,
+    schema: *const std::os::raw::c_char,
+}
+
+/// Run `f` behind `catch_unwind` so a panic anywhere in a JNI call (an `.unwrap()`
+/// on a JNI op, an indexing bug, etc.) can't unwind across the `extern "system"`
+/// boundary and abort the JVM. On a caught panic, throw a `RuntimeException` with
+/// the panic message and return `sentinel` instead.
+fn panic_guard<T>(env: &JNIEnv, sentinel: T, f: impl FnOnce() -> T) -> T {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = panic_message(&*payload);
+            let _ = env.throw_new("java/lang/RuntimeException", message);
+            sentinel
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "native Harmony call panicked".to_string()
+    }
+}
+
+/// Throw a `HarmonyException` for a failed `HarmonyResult`, using its `error_message`
+/// as the diagnostic if one was set (freeing it afterwards), or `default_msg` otherwise.
+unsafe fn throw_harmony_error(env: &JNIEnv, result: &HarmonyResult, default_msg: &str) {
+    if !result.error_message.is_null() {
+        let message = CStr::from_ptr(result.error_message)
+            .to_str()
+            .unwrap_or(default_msg)
+            .to_string();
+        harmony_free_string(result.error_message);
+        let _ = env.throw_new("ai/noesisreality/harmony/HarmonyException", message);
+    } else {
+        let _ = env.throw_new("ai/noesisreality/harmony/HarmonyException", default_msg);
+    }
+}
+
 /// Create a new Harmony encoder
 #[no_mangle]
 pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeCreateEncoder(
-    _env: JNIEnv,
+    env: JNIEnv,
     _class: JClass,
 ) -> jlong {
-    unsafe {
-        let encoder = harmony_encoding_new();
-        encoder as jlong
-    }
+    panic_guard(&env, 0, || {
+        unsafe {
+            let encoder = harmony_encoding_new();
+            encoder as jlong
+        }
+    })
 }
 
 /// Free a Harmony encoder
 #[no_mangle]
 pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeFreeEncoder(
-    _env: JNIEnv,
+    env: JNIEnv,
     _class: JClass,
     encoder_ptr: jlong,
 ) {
-    if encoder_ptr != 0 {
-        unsafe {
-            harmony_encoding_free(encoder_ptr as *mut std::ffi::c_void);
+    panic_guard(&env, (), || {
+        if encoder_ptr != 0 {
+            unsafe {
+                harmony_encoding_free(encoder_ptr as *mut std::ffi::c_void);
+            }
         }
-    }
+    })
 }
 
 /// Encode plain text without Harmony formatting
@@ -77,56 +209,56 @@ pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeEncodeP
     encoder_ptr: jlong,
     text: JString,
 ) -> jintArray {
-    let encoder = encoder_ptr as *const std::ffi::c_void;
-    if encoder.is_null() {
-        return ptr::null_mut();
-    }
-
-    // Convert Java string to C string
-    let text_str = match env.get_string(text) {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
-    };
-    
-    let c_text = match CString::new(text_str.to_str().unwrap_or("")) {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
-    };
-
-    unsafe {
-        let mut tokens_ptr: *mut u32 = ptr::null_mut();
-        let mut tokens_len: usize = 0;
-
-        let result = harmony_encoding_encode_plain(
-            encoder,
-            c_text.as_ptr(),
-            &mut tokens_ptr,
-            &mut tokens_len,
-        );
-
-        if !result.success {
-            if !result.error_message.is_null() {
-                harmony_free_string(result.error_message);
-            }
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
             return ptr::null_mut();
         }
 
-        if tokens_ptr.is_null() || tokens_len == 0 {
-            return ptr::null_mut();
-        }
+        // Convert Java string to C string
+        let text_str = match env.get_string(text) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        let c_text = match CString::new(text_str.to_str().unwrap_or("")) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
 
-        // Convert to Java int array
-        let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
-        let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
-        
-        let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
-        env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
 
-        // Free the native tokens
-        harmony_free_tokens(tokens_ptr, tokens_len);
+            let result = harmony_encoding_encode_plain(
+                encoder,
+                c_text.as_ptr(),
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
 
-        result_array
-    }
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
+
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
+        }
+    })
 }
 
 /// Render a structured Harmony prompt
@@ -139,83 +271,203 @@ pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeRenderP
     user_message: JString,
     assistant_prefix: JString,
 ) -> jintArray {
-    let encoder = encoder_ptr as *const std::ffi::c_void;
-    if encoder.is_null() {
-        return ptr::null_mut();
-    }
-
-    // Convert Java strings to C strings
-    let user_str = match env.get_string(user_message) {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
-    };
-    let c_user = match CString::new(user_str.to_str().unwrap_or("")) {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
-    };
-
-    // Handle optional system message
-    let c_system = if system_message.is_null() {
-        None
-    } else {
-        match env.get_string(system_message) {
-            Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
-                Ok(cs) => Some(cs),
-                Err(_) => return ptr::null_mut(),
-            },
-            Err(_) => None,
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
+            return ptr::null_mut();
         }
-    };
 
-    // Handle optional assistant prefix
-    let c_assistant = if assistant_prefix.is_null() {
-        None
-    } else {
-        match env.get_string(assistant_prefix) {
-            Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
-                Ok(cs) => Some(cs),
-                Err(_) => return ptr::null_mut(),
-            },
-            Err(_) => None,
-        }
-    };
-
-    unsafe {
-        let mut tokens_ptr: *mut u32 = ptr::null_mut();
-        let mut tokens_len: usize = 0;
-
-        let result = harmony_encoding_render_prompt(
-            encoder,
-            c_system.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
-            c_user.as_ptr(),
-            c_assistant.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
-            &mut tokens_ptr,
-            &mut tokens_len,
-        );
-
-        if !result.success {
-            if !result.error_message.is_null() {
-                harmony_free_string(result.error_message);
+        // Convert Java strings to C strings
+        let user_str = match env.get_string(user_message) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_user = match CString::new(user_str.to_str().unwrap_or("")) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        // Handle optional system message
+        let c_system = if system_message.is_null() {
+            None
+        } else {
+            match env.get_string(system_message) {
+                Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
+                    Ok(cs) => Some(cs),
+                    Err(_) => return ptr::null_mut(),
+                },
+                Err(_) => None,
             }
-            return ptr::null_mut();
+        };
+
+        // Handle optional assistant prefix
+        let c_assistant = if assistant_prefix.is_null() {
+            None
+        } else {
+            match env.get_string(assistant_prefix) {
+                Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
+                    Ok(cs) => Some(cs),
+                    Err(_) => return ptr::null_mut(),
+                },
+                Err(_) => None,
+            }
+        };
+
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
+
+            let result = harmony_encoding_render_prompt(
+                encoder,
+                c_system.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                c_user.as_ptr(),
+                c_assistant.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
+
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
         }
+    })
+}
 
-        if tokens_ptr.is_null() || tokens_len == 0 {
+/// Render a full multi-turn Harmony conversation from parallel role/channel/content
+/// arrays (one entry per message). `channels` entries may be null for messages that
+/// don't target a specific channel.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeRenderConversation(
+    env: JNIEnv,
+    _class: JClass,
+    encoder_ptr: jlong,
+    roles: JObjectArray,
+    channels: JObjectArray,
+    contents: JObjectArray,
+) -> jintArray {
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
             return ptr::null_mut();
         }
 
-        // Convert to Java int array
-        let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
-        let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
-        
-        let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
-        env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+        let message_count = match env.get_array_length(roles) {
+            Ok(len) => len as usize,
+            Err(_) => return ptr::null_mut(),
+        };
 
-        // Free the native tokens
-        harmony_free_tokens(tokens_ptr, tokens_len);
+        // Keep the backing CStrings alive for the duration of the native call.
+        let mut c_roles: Vec<CString> = Vec::with_capacity(message_count);
+        let mut c_channels: Vec<Option<CString>> = Vec::with_capacity(message_count);
+        let mut c_contents: Vec<CString> = Vec::with_capacity(message_count);
 
-        result_array
-    }
+        for i in 0..message_count {
+            let role_obj = match env.get_object_array_element(roles, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let role_str = match env.get_string(JString::from(role_obj)) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_role = match CString::new(role_str.to_str().unwrap_or("")) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            c_roles.push(c_role);
+
+            let channel_obj = match env.get_object_array_element(channels, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_channel = if channel_obj.is_null() {
+                None
+            } else {
+                let channel_str = match env.get_string(JString::from(channel_obj)) {
+                    Ok(s) => s,
+                    Err(_) => return ptr::null_mut(),
+                };
+                match CString::new(channel_str.to_str().unwrap_or("")) {
+                    Ok(s) => Some(s),
+                    Err(_) => return ptr::null_mut(),
+                }
+            };
+            c_channels.push(c_channel);
+
+            let content_obj = match env.get_object_array_element(contents, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let content_str = match env.get_string(JString::from(content_obj)) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_content = match CString::new(content_str.to_str().unwrap_or("")) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            c_contents.push(c_content);
+        }
+
+        let messages: Vec<HarmonyMessage> = (0..message_count)
+            .map(|i| HarmonyMessage {
+                role: c_roles[i].as_ptr(),
+                channel: c_channels[i].as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                content: c_contents[i].as_ptr(),
+            })
+            .collect();
+
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
+
+            let result = harmony_encoding_render_conversation(
+                encoder,
+                messages.as_ptr(),
+                messages.len(),
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
+
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
+        }
+    })
 }
 
 /// Decode tokens back to text
@@ -226,43 +478,45 @@ pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeDecode(
     encoder_ptr: jlong,
     tokens: JIntArray,
 ) -> jstring {
-    let encoder = encoder_ptr as *const std::ffi::c_void;
-    if encoder.is_null() {
-        return ptr::null_mut();
-    }
-
-    // Convert Java int array to native u32 array
-    let tokens_len = env.get_array_length(tokens).unwrap() as usize;
-    let mut java_tokens = vec![0i32; tokens_len];
-    env.get_int_array_region(tokens, 0, &mut java_tokens).unwrap();
-    
-    let native_tokens: Vec<u32> = java_tokens.iter().map(|&t| t as u32).collect();
-
-    unsafe {
-        let text_ptr = harmony_encoding_decode(
-            encoder,
-            native_tokens.as_ptr(),
-            native_tokens.len(),
-        );
-
-        if text_ptr.is_null() {
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
             return ptr::null_mut();
         }
 
-        let c_str = CStr::from_ptr(text_ptr);
-        let text = match c_str.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                harmony_free_string(text_ptr);
+        // Convert Java int array to native u32 array
+        let tokens_len = env.get_array_length(tokens).unwrap() as usize;
+        let mut java_tokens = vec![0i32; tokens_len];
+        env.get_int_array_region(tokens, 0, &mut java_tokens).unwrap();
+
+        let native_tokens: Vec<u32> = java_tokens.iter().map(|&t| t as u32).collect();
+
+        unsafe {
+            let text_ptr = harmony_encoding_decode(
+                encoder,
+                native_tokens.as_ptr(),
+                native_tokens.len(),
+            );
+
+            if text_ptr.is_null() {
                 return ptr::null_mut();
             }
-        };
 
-        let result = env.new_string(text).unwrap();
-        harmony_free_string(text_ptr);
+            let c_str = CStr::from_ptr(text_ptr);
+            let text = match c_str.to_str() {
+                Ok(s) => s,
+                Err(_) => {
+                    harmony_free_string(text_ptr);
+                    return ptr::null_mut();
+                }
+            };
 
-        result.into_inner()
-    }
+            let result = env.new_string(text).unwrap();
+            harmony_free_string(text_ptr);
+
+            result.into_inner()
+        }
+    })
 }
 
 /// Get stop tokens
@@ -272,42 +526,578 @@ pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeGetStop
     _class: JClass,
     encoder_ptr: jlong,
 ) -> jintArray {
-    let encoder = encoder_ptr as *const std::ffi::c_void;
-    if encoder.is_null() {
-        return ptr::null_mut();
-    }
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
+            return ptr::null_mut();
+        }
+
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
+
+            let result = harmony_encoding_stop_tokens(
+                encoder,
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
 
-    unsafe {
-        let mut tokens_ptr: *mut u32 = ptr::null_mut();
-        let mut tokens_len: usize = 0;
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
+        }
+    })
+}
+
+/// Create an incremental stream parser bound to an encoder. Tokens are fed to it
+/// one at a time via `nativeStreamPush` as they're generated.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeParseStreaming(
+    env: JNIEnv,
+    _class: JClass,
+    encoder_ptr: jlong,
+) -> jlong {
+    panic_guard(&env, 0, || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
+            return 0;
+        }
 
-        let result = harmony_encoding_stop_tokens(
-            encoder,
-            &mut tokens_ptr,
-            &mut tokens_len,
-        );
+        unsafe {
+            let parser = harmony_stream_parser_new(encoder);
+            parser as jlong
+        }
+    })
+}
 
-        if !result.success {
-            if !result.error_message.is_null() {
-                harmony_free_string(result.error_message);
+/// Free a stream parser created by `nativeParseStreaming`
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeFreeStreamParser(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+) {
+    panic_guard(&env, (), || {
+        if parser_ptr != 0 {
+            unsafe {
+                harmony_stream_parser_free(parser_ptr as *mut std::ffi::c_void);
             }
+        }
+    })
+}
+
+/// Push one generated token into the stream parser and return the newly-decoded
+/// text delta. Multi-byte tokens may split a UTF-8 codepoint, so the native parser
+/// buffers incomplete byte sequences and only surfaces valid UTF-8 here; the delta
+/// may be empty if the token didn't complete a codepoint. Call
+/// `nativeStreamChannelBoundaryCrossed` / `nativeStreamMessageBoundaryCrossed` right
+/// after this to check whether a channel or message boundary was just crossed.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeStreamPush(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+    token: jint,
+) -> jstring {
+    panic_guard(&env, ptr::null_mut(), || {
+        let parser = parser_ptr as *mut std::ffi::c_void;
+        if parser.is_null() {
             return ptr::null_mut();
         }
 
-        if tokens_ptr.is_null() || tokens_len == 0 {
+        unsafe {
+            let mut text_ptr: *mut std::os::raw::c_char = ptr::null_mut();
+
+            let result = harmony_stream_parser_push(parser, token as u32, &mut text_ptr);
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony stream parsing failed");
+                return ptr::null_mut();
+            }
+
+            if text_ptr.is_null() {
+                return env.new_string("").unwrap().into_inner();
+            }
+
+            let c_str = CStr::from_ptr(text_ptr);
+            let text = match c_str.to_str() {
+                Ok(s) => s,
+                Err(_) => {
+                    harmony_free_string(text_ptr);
+                    return ptr::null_mut();
+                }
+            };
+
+            let result = env.new_string(text).unwrap();
+            harmony_free_string(text_ptr);
+
+            result.into_inner()
+        }
+    })
+}
+
+/// Whether the most recent `nativeStreamPush` call crossed a channel boundary
+/// (i.e. the parser moved from one channel into another, such as `analysis` to `final`)
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeStreamChannelBoundaryCrossed(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+) -> jboolean {
+    panic_guard(&env, 0, || {
+        let parser = parser_ptr as *const std::ffi::c_void;
+        if parser.is_null() {
+            return 0;
+        }
+
+        unsafe { harmony_stream_parser_channel_boundary(parser) as jboolean }
+    })
+}
+
+/// Whether the most recent `nativeStreamPush` call crossed a message boundary
+/// (`<|end|>`, `<|return|>`, or `<|call|>`)
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeStreamMessageBoundaryCrossed(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+) -> jboolean {
+    panic_guard(&env, 0, || {
+        let parser = parser_ptr as *const std::ffi::c_void;
+        if parser.is_null() {
+            return 0;
+        }
+
+        unsafe { harmony_stream_parser_message_boundary(parser) as jboolean }
+    })
+}
+
+/// The channel (`analysis`, `commentary`, or `final`) the stream parser currently
+/// believes it is in, or null if no channel has been established yet
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeStreamCurrentChannel(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+) -> jstring {
+    panic_guard(&env, ptr::null_mut(), || {
+        let parser = parser_ptr as *const std::ffi::c_void;
+        if parser.is_null() {
+            return ptr::null_mut();
+        }
+
+        unsafe {
+            let channel_ptr = harmony_stream_parser_current_channel(parser);
+            if channel_ptr.is_null() {
+                return ptr::null_mut();
+            }
+
+            let c_str = CStr::from_ptr(channel_ptr);
+            let channel = match c_str.to_str() {
+                Ok(s) => s,
+                Err(_) => {
+                    harmony_free_string(channel_ptr);
+                    return ptr::null_mut();
+                }
+            };
+
+            let result = env.new_string(channel).unwrap();
+            harmony_free_string(channel_ptr);
+
+            result.into_inner()
+        }
+    })
+}
+
+/// The role (`assistant`, `tool`, etc.) the stream parser currently believes
+/// produced the message in progress, or null if no role has been established yet
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeStreamCurrentRole(
+    env: JNIEnv,
+    _class: JClass,
+    parser_ptr: jlong,
+) -> jstring {
+    panic_guard(&env, ptr::null_mut(), || {
+        let parser = parser_ptr as *const std::ffi::c_void;
+        if parser.is_null() {
+            return ptr::null_mut();
+        }
+
+        unsafe {
+            let role_ptr = harmony_stream_parser_current_role(parser);
+            if role_ptr.is_null() {
+                return ptr::null_mut();
+            }
+
+            let c_str = CStr::from_ptr(role_ptr);
+            let role = match c_str.to_str() {
+                Ok(s) => s,
+                Err(_) => {
+                    harmony_free_string(role_ptr);
+                    return ptr::null_mut();
+                }
+            };
+
+            let result = env.new_string(role).unwrap();
+            harmony_free_string(role_ptr);
+
+            result.into_inner()
+        }
+    })
+}
+
+/// Render a developer instruction plus a set of callable tool declarations into a
+/// Harmony prompt. `tool_names`/`tool_descriptions`/`tool_schemas` are parallel
+/// arrays (one entry per tool); `developer_instruction` may be null.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeRenderWithTools(
+    env: JNIEnv,
+    _class: JClass,
+    encoder_ptr: jlong,
+    developer_instruction: JString,
+    tool_names: JObjectArray,
+    tool_descriptions: JObjectArray,
+    tool_schemas: JObjectArray,
+) -> jintArray {
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
+            return ptr::null_mut();
+        }
+
+        let c_instruction = if developer_instruction.is_null() {
+            None
+        } else {
+            match env.get_string(developer_instruction) {
+                Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
+                    Ok(cs) => Some(cs),
+                    Err(_) => return ptr::null_mut(),
+                },
+                Err(_) => return ptr::null_mut(),
+            }
+        };
+
+        let tool_count = match env.get_array_length(tool_names) {
+            Ok(len) => len as usize,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        // Keep the backing CStrings alive for the duration of the native call.
+        let mut c_names: Vec<CString> = Vec::with_capacity(tool_count);
+        let mut c_descriptions: Vec<CString> = Vec::with_capacity(tool_count);
+        let mut c_schemas: Vec<CString> = Vec::with_capacity(tool_count);
+
+        for i in 0..tool_count {
+            let name_obj = match env.get_object_array_element(tool_names, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let name_str = match env.get_string(JString::from(name_obj)) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_name = match CString::new(name_str.to_str().unwrap_or("")) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            c_names.push(c_name);
+
+            let description_obj = match env.get_object_array_element(tool_descriptions, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let description_str = match env.get_string(JString::from(description_obj)) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_description = match CString::new(description_str.to_str().unwrap_or("")) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            c_descriptions.push(c_description);
+
+            let schema_obj = match env.get_object_array_element(tool_schemas, i as i32) {
+                Ok(o) => o,
+                Err(_) => return ptr::null_mut(),
+            };
+            let schema_str = match env.get_string(JString::from(schema_obj)) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            let c_schema = match CString::new(schema_str.to_str().unwrap_or("")) {
+                Ok(s) => s,
+                Err(_) => return ptr::null_mut(),
+            };
+            c_schemas.push(c_schema);
+        }
+
+        let tools: Vec<HarmonyToolDef> = (0..tool_count)
+            .map(|i| HarmonyToolDef {
+                name: c_names[i].as_ptr(),
+                description: c_descriptions[i].as_ptr(),
+                schema: c_schemas[i].as_ptr(),
+            })
+            .collect();
+
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
+
+            let result = harmony_encoding_render_with_tools(
+                encoder,
+                c_instruction.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                tools.as_ptr(),
+                tools.len(),
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
+
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
+        }
+    })
+}
+
+/// Scan a decoded token array for `commentary`/`to=`/`<|call|>` tool invocations and
+/// return them to Java as a flattened `String[]` of `[name0, args0, name1, args1, ...]`
+/// pairs, where `argsN` is the raw (unparsed) JSON argument string for call `N`.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeParseToolCalls(
+    env: JNIEnv,
+    _class: JClass,
+    encoder_ptr: jlong,
+    tokens: JIntArray,
+) -> jobjectArray {
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
             return ptr::null_mut();
         }
 
-        // Convert to Java int array
-        let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
-        let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
-        
-        let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
-        env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+        let tokens_len = env.get_array_length(tokens).unwrap() as usize;
+        let mut java_tokens = vec![0i32; tokens_len];
+        env.get_int_array_region(tokens, 0, &mut java_tokens).unwrap();
+
+        let native_tokens: Vec<u32> = java_tokens.iter().map(|&t| t as u32).collect();
+
+        unsafe {
+            let mut names_ptr: *mut *mut std::os::raw::c_char = ptr::null_mut();
+            let mut args_ptr: *mut *mut std::os::raw::c_char = ptr::null_mut();
+            let mut call_count: usize = 0;
+
+            let result = harmony_decode_tool_calls(
+                encoder,
+                native_tokens.as_ptr(),
+                native_tokens.len(),
+                &mut names_ptr,
+                &mut args_ptr,
+                &mut call_count,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony tool call parsing failed");
+                return ptr::null_mut();
+            }
+
+            let result_array = env
+                .new_object_array(
+                    (call_count * 2) as i32,
+                    "java/lang/String",
+                    JObject::null(),
+                )
+                .unwrap();
+
+            if call_count > 0 {
+                let names = std::slice::from_raw_parts(names_ptr, call_count);
+                let args = std::slice::from_raw_parts(args_ptr, call_count);
 
-        // Free the native tokens
-        harmony_free_tokens(tokens_ptr, tokens_len);
+                for i in 0..call_count {
+                    let name = CStr::from_ptr(names[i]).to_str().unwrap_or("");
+                    let arg = CStr::from_ptr(args[i]).to_str().unwrap_or("");
 
-        result_array
+                    let name_str = env.new_string(name).unwrap();
+                    let arg_str = env.new_string(arg).unwrap();
+
+                    env.set_object_array_element(result_array, (i * 2) as i32, name_str).unwrap();
+                    env.set_object_array_element(result_array, (i * 2 + 1) as i32, arg_str).unwrap();
+                }
+
+                harmony_free_tool_calls(names_ptr, args_ptr, call_count);
+            }
+
+            result_array
+        }
+    })
+}
+
+/// Reasoning-effort levels Harmony can inject into the rendered system header,
+/// controlling how much chain-of-thought the model spends before answering.
+pub const REASONING_EFFORT_LOW: jint = 0;
+pub const REASONING_EFFORT_MEDIUM: jint = 1;
+pub const REASONING_EFFORT_HIGH: jint = 2;
+
+/// Validate an incoming reasoning-effort value against the `REASONING_EFFORT_*` range.
+/// Returns `Err` with a diagnostic message if it's out of range, instead of silently
+/// substituting a different effort level than the caller asked for.
+fn validate_reasoning_effort(effort: jint) -> Result<jint, String> {
+    if (REASONING_EFFORT_LOW..=REASONING_EFFORT_HIGH).contains(&effort) {
+        Ok(effort)
+    } else {
+        Err(format!(
+            "reasoning_effort {} is out of range (expected {}-{})",
+            effort, REASONING_EFFORT_LOW, REASONING_EFFORT_HIGH
+        ))
     }
-}
\ No newline at end of file
+}
+
+/// Render a structured Harmony prompt with explicit system-message metadata:
+/// model identity, knowledge-cutoff date, current date, and reasoning-effort
+/// level (`REASONING_EFFORT_LOW`/`_MEDIUM`/`_HIGH`). `model_identity`,
+/// `knowledge_cutoff`, and `current_date` may be null to let Harmony fall back
+/// to its defaults.
+#[no_mangle]
+pub extern "system" fn Java_ai_noesisreality_harmony_HarmonyEngine_nativeRenderPromptEx(
+    env: JNIEnv,
+    _class: JClass,
+    encoder_ptr: jlong,
+    system_message: JString,
+    user_message: JString,
+    assistant_prefix: JString,
+    model_identity: JString,
+    knowledge_cutoff: JString,
+    current_date: JString,
+    reasoning_effort: jint,
+) -> jintArray {
+    panic_guard(&env, ptr::null_mut(), || {
+        let encoder = encoder_ptr as *const std::ffi::c_void;
+        if encoder.is_null() {
+            return ptr::null_mut();
+        }
+
+        // Convert Java strings to C strings
+        let user_str = match env.get_string(user_message) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_user = match CString::new(user_str.to_str().unwrap_or("")) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        let optional_string = |env: &JNIEnv, value: JString| -> Result<Option<CString>, ()> {
+            if value.is_null() {
+                return Ok(None);
+            }
+            match env.get_string(value) {
+                Ok(s) => match CString::new(s.to_str().unwrap_or("")) {
+                    Ok(cs) => Ok(Some(cs)),
+                    Err(_) => Err(()),
+                },
+                Err(_) => Err(()),
+            }
+        };
+
+        let c_system = match optional_string(&env, system_message) {
+            Ok(v) => v,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_assistant = match optional_string(&env, assistant_prefix) {
+            Ok(v) => v,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_model_identity = match optional_string(&env, model_identity) {
+            Ok(v) => v,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_knowledge_cutoff = match optional_string(&env, knowledge_cutoff) {
+            Ok(v) => v,
+            Err(_) => return ptr::null_mut(),
+        };
+        let c_current_date = match optional_string(&env, current_date) {
+            Ok(v) => v,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        let reasoning_effort = match validate_reasoning_effort(reasoning_effort) {
+            Ok(v) => v,
+            Err(message) => {
+                let _ = env.throw_new("ai/noesisreality/harmony/HarmonyException", message);
+                return ptr::null_mut();
+            }
+        };
+
+        unsafe {
+            let mut tokens_ptr: *mut u32 = ptr::null_mut();
+            let mut tokens_len: usize = 0;
+
+            let result = harmony_encoding_render_prompt_ex(
+                encoder,
+                c_system.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                c_user.as_ptr(),
+                c_assistant.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                c_model_identity.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                c_knowledge_cutoff.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                c_current_date.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                reasoning_effort,
+                &mut tokens_ptr,
+                &mut tokens_len,
+            );
+
+            if !result.success {
+                throw_harmony_error(&env, &result, "Harmony encoding failed");
+                return ptr::null_mut();
+            }
+
+            if tokens_ptr.is_null() || tokens_len == 0 {
+                return ptr::null_mut();
+            }
+
+            // Convert to Java int array
+            let tokens_slice = std::slice::from_raw_parts(tokens_ptr, tokens_len);
+            let java_tokens: Vec<i32> = tokens_slice.iter().map(|&t| t as i32).collect();
+
+            let result_array = env.new_int_array(java_tokens.len() as i32).unwrap();
+            env.set_int_array_region(result_array, 0, &java_tokens).unwrap();
+
+            // Free the native tokens
+            harmony_free_tokens(tokens_ptr, tokens_len);
+
+            result_array
+        }
+    })
+}